@@ -6,6 +6,11 @@ use core::{fmt, ops::RangeInclusive, str};
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
+#[cfg(feature = "der")]
+use der::{
+    asn1::OctetStringRef, DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, Writer,
+};
+
 /// Ranges within [`Bytes`] which correspond to CRUID fields.
 const BYTES_RANGES: &[RangeInclusive<usize>] = &[0..=3, 4..=5, 6..=7, 8..=9, 10..=15];
 
@@ -67,6 +72,14 @@ impl Cruid {
         Self { bytes: output }
     }
 
+    /// Generate a random CRUID, filling all 16 bytes from a CSPRNG.
+    #[cfg(feature = "getrandom")]
+    pub fn random() -> Cruid {
+        let mut bytes = Bytes::default();
+        getrandom::getrandom(&mut bytes).expect("RNG failure");
+        Self::from_bytes(&bytes)
+    }
+
     /// Decode the hex fields in a CRUID into raw bytes.
     pub fn to_bytes(&self) -> Bytes {
         let mut ret = Bytes::default();
@@ -124,6 +137,100 @@ impl From<Uuid> for Cruid {
     }
 }
 
+#[cfg(feature = "der")]
+impl FixedTag for Cruid {
+    const TAG: Tag = Tag::OctetString;
+}
+
+#[cfg(feature = "der")]
+impl EncodeValue for Cruid {
+    fn value_len(&self) -> der::Result<Length> {
+        OctetStringRef::new(&self.to_bytes())?.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        OctetStringRef::new(&self.to_bytes())?.encode_value(writer)
+    }
+}
+
+#[cfg(feature = "der")]
+impl<'a> DecodeValue<'a> for Cruid {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let octets = OctetStringRef::decode_value(reader, header)?;
+        let bytes =
+            Bytes::try_from(octets.as_bytes()).map_err(|_| Tag::OctetString.length_error())?;
+        Ok(Cruid::from_bytes(&bytes))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cruid {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.as_str())
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Cruid {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        struct CruidVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CruidVisitor {
+            type Value = Cruid;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a CRUID string or 16-byte array")
+            }
+
+            fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Cruid::parse(v).map_err(E::custom)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> core::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                let bytes = Bytes::try_from(v).map_err(|_| E::custom(Error::Length))?;
+                Ok(Cruid::from_bytes(&bytes))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = Bytes::default();
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                Ok(Cruid::from_bytes(&bytes))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(CruidVisitor)
+        } else {
+            deserializer.deserialize_bytes(CruidVisitor)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Bytes, Cruid, Error};
@@ -164,4 +271,55 @@ mod tests {
         let uuid = uuid::Uuid::from(cruid);
         assert_eq!(cruid, Cruid::from(uuid));
     }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn random_smoke() {
+        let a = Cruid::random();
+        let b = Cruid::random();
+        assert_ne!(a, b);
+        assert!(Cruid::parse(a.as_str()).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_human_readable_round_trip() {
+        let cruid = Cruid::parse(EXAMPLE_CRUID).unwrap();
+        let json = serde_json::to_string(&cruid).unwrap();
+        assert_eq!(json, format!("\"{}\"", EXAMPLE_CRUID));
+        assert_eq!(serde_json::from_str::<Cruid>(&json).unwrap(), cruid);
+    }
+
+    #[cfg(feature = "der")]
+    #[test]
+    fn der_round_trip() {
+        use der::{Decode, Encode};
+
+        let cruid = Cruid::parse(EXAMPLE_CRUID).unwrap();
+        let der_bytes = cruid.to_der().unwrap();
+        assert_eq!(Cruid::from_der(&der_bytes).unwrap(), cruid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_binary_round_trip() {
+        let cruid = Cruid::parse(EXAMPLE_CRUID).unwrap();
+        let bytes = bincode::serialize(&cruid).unwrap();
+        assert_eq!(bincode::deserialize::<Cruid>(&bytes).unwrap(), cruid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_binary_deserializes_byte_sequence() {
+        use serde_test::{assert_de_tokens, Configure, Token};
+
+        let cruid = Cruid::parse(EXAMPLE_CRUID).unwrap();
+        let mut tokens = vec![Token::Seq {
+            len: Some(EXAMPLE_BYTES.len()),
+        }];
+        tokens.extend(EXAMPLE_BYTES.iter().map(|byte| Token::U8(*byte)));
+        tokens.push(Token::SeqEnd);
+
+        assert_de_tokens(&cruid.compact(), &tokens);
+    }
 }
@@ -5,6 +5,48 @@ use aes::{
     cipher::{BlockDecrypt, BlockEncrypt, KeyInit},
     Aes128, Block,
 };
+#[cfg(feature = "context")]
+use cmac::{Cmac, Mac};
+#[cfg(feature = "context")]
+use subtle::ConstantTimeEq;
+
+#[cfg(feature = "kdf")]
+use argon2::{Algorithm, Argon2, Params, Version};
+#[cfg(feature = "kdf")]
+use zeroize::Zeroize;
+
+/// Fixed block encrypted under the AES key to derive the CMAC subkey used
+/// by [`EncryptionKey::encrypt_with_context`] and
+/// [`EncryptionKey::decrypt_with_context`].
+#[cfg(feature = "context")]
+const MAC_SUBKEY_CONSTANT: [u8; 16] = [0xff; 16];
+
+/// Tunable Argon2id cost parameters for
+/// [`EncryptionKey::derive_from_password_with_params`].
+#[cfg(feature = "kdf")]
+#[derive(Copy, Clone, Debug)]
+pub struct KdfParams {
+    /// Memory cost in KiB.
+    pub m_cost: u32,
+
+    /// Number of iterations.
+    pub t_cost: u32,
+
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+#[cfg(feature = "kdf")]
+impl Default for KdfParams {
+    /// OWASP-recommended Argon2id baseline: 19 MiB, 2 iterations, 1 lane.
+    fn default() -> Self {
+        Self {
+            m_cost: 19 * 1024,
+            t_cost: 2,
+            p_cost: 1,
+        }
+    }
+}
 
 /// CRUID-AES-128 encryption key.
 pub struct EncryptionKey(Aes128);
@@ -26,6 +68,18 @@ impl EncryptionKey {
         Cruid::from_bytes(&block.into())
     }
 
+    /// Encrypt a randomly generated 64-bit integer, returning a [`Cruid`].
+    ///
+    /// The plaintext half of the block is filled from a CSPRNG, giving a
+    /// collision-resistant unique identifier without the caller having to
+    /// manage its own counter or entropy source.
+    #[cfg(feature = "getrandom")]
+    pub fn encrypt_random(&self) -> Cruid {
+        let mut plaintext = [0u8; 8];
+        getrandom::getrandom(&mut plaintext).expect("RNG failure");
+        self.encrypt(u64::from_le_bytes(plaintext))
+    }
+
     /// Decrypt the given [`Cruid`], returning a 64-bit integer if it
     /// authenticates successfully under this key.
     pub fn decrypt(&self, cruid: &Cruid) -> Result<u64> {
@@ -42,4 +96,186 @@ impl EncryptionKey {
             Err(Error::Decryption)
         }
     }
+
+    /// Encrypt the given 64-bit integer, binding the result to `aad` so
+    /// that it only authenticates within its intended namespace (e.g. a
+    /// table name or tenant ID), returning a [`Cruid`].
+    ///
+    /// The tag half of the block is an AES-CMAC over `aad`, computed under
+    /// a subkey derived from this encryption key and truncated to 8 bytes,
+    /// rather than the fixed zero tag used by [`EncryptionKey::encrypt`].
+    /// An empty `aad` yields the zero tag, so the result is then the same as
+    /// [`EncryptionKey::encrypt`].
+    #[cfg(feature = "context")]
+    pub fn encrypt_with_context(&self, plaintext: u64, aad: &[u8]) -> Cruid {
+        let mut block = Block::default();
+        block[..8].copy_from_slice(&plaintext.to_le_bytes());
+        block[8..].copy_from_slice(&self.derive_tag(aad));
+        self.0.encrypt_block(&mut block);
+        Cruid::from_bytes(&block.into())
+    }
+
+    /// Decrypt the given [`Cruid`], returning a 64-bit integer if it
+    /// authenticates successfully under this key and `aad`.
+    #[cfg(feature = "context")]
+    pub fn decrypt_with_context(&self, cruid: &Cruid, aad: &[u8]) -> Result<u64> {
+        let mut block = Block::from(cruid.to_bytes());
+        self.0.decrypt_block(&mut block);
+
+        let (a, b) = block.split_at(8);
+        let value = u64::from_le_bytes(a.try_into()?);
+        let expected_tag = self.derive_tag(aad);
+
+        if b.ct_eq(&expected_tag).into() {
+            Ok(value)
+        } else {
+            Err(Error::Decryption)
+        }
+    }
+
+    /// Derive the 8-byte tag for `aad`: AES-CMAC over `aad`, truncated to
+    /// 8 bytes, under a subkey obtained by encrypting a fixed constant
+    /// block with this encryption key.
+    ///
+    /// An empty `aad` is treated as the existing zero-tag mode, so that
+    /// [`EncryptionKey::encrypt`]/[`EncryptionKey::decrypt`] and the
+    /// context-bound variants interoperate when no context is supplied.
+    #[cfg(feature = "context")]
+    fn derive_tag(&self, aad: &[u8]) -> [u8; 8] {
+        if aad.is_empty() {
+            return [0u8; 8];
+        }
+
+        let mut subkey_block = Block::from(MAC_SUBKEY_CONSTANT);
+        self.0.encrypt_block(&mut subkey_block);
+
+        let mut mac = Cmac::<Aes128>::new_from_slice(&subkey_block).expect("valid key size");
+        mac.update(aad);
+
+        let mut tag = [0u8; 8];
+        tag.copy_from_slice(&mac.finalize().into_bytes()[..8]);
+        tag
+    }
+
+    /// Derive an [`EncryptionKey`] from a password and salt using Argon2id
+    /// with [`KdfParams::default`] cost parameters.
+    ///
+    /// This stretches a caller-supplied passphrase into a 16-byte AES-128
+    /// key, so that CRUID encryption/decryption can be gated behind a user
+    /// secret instead of requiring the caller to manage a raw key. Use
+    /// [`EncryptionKey::derive_from_password_with_params`] to tune the cost.
+    #[cfg(feature = "kdf")]
+    pub fn derive_from_password(password: &[u8], salt: &[u8]) -> Result<Self> {
+        Self::derive_from_password_with_params(password, salt, KdfParams::default())
+    }
+
+    /// As [`EncryptionKey::derive_from_password`], with tunable cost
+    /// parameters.
+    #[cfg(feature = "kdf")]
+    pub fn derive_from_password_with_params(
+        password: &[u8],
+        salt: &[u8],
+        params: KdfParams,
+    ) -> Result<Self> {
+        let argon2_params = Params::new(
+            params.m_cost,
+            params.t_cost,
+            params.p_cost,
+            Some(Self::BYTE_SIZE),
+        )
+        .map_err(|_| Error::KeyDerivation)?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
+
+        let mut key_bytes = [0u8; Self::BYTE_SIZE];
+        let result = argon2
+            .hash_password_into(password, salt, &mut key_bytes)
+            .map(|()| Self::new(&key_bytes))
+            .map_err(|_| Error::KeyDerivation);
+
+        key_bytes.zeroize();
+        result
+    }
+}
+
+#[cfg(all(test, any(feature = "getrandom", feature = "context", feature = "kdf")))]
+mod tests {
+    use super::EncryptionKey;
+    #[cfg(feature = "kdf")]
+    use super::KdfParams;
+
+    const EXAMPLE_KEY: [u8; EncryptionKey::BYTE_SIZE] =
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn encrypt_random_round_trip() {
+        let key = EncryptionKey::new(&EXAMPLE_KEY);
+        let cruid = key.encrypt_random();
+        assert!(key.decrypt(&cruid).is_ok());
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn context_round_trip() {
+        let key = EncryptionKey::new(&EXAMPLE_KEY);
+        let cruid = key.encrypt_with_context(42, b"tenantA");
+        assert_eq!(key.decrypt_with_context(&cruid, b"tenantA").unwrap(), 42);
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn context_rejects_wrong_aad() {
+        let key = EncryptionKey::new(&EXAMPLE_KEY);
+        let cruid = key.encrypt_with_context(42, b"tenantA");
+        assert!(key.decrypt_with_context(&cruid, b"tenantB").is_err());
+        assert!(key.decrypt(&cruid).is_err());
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn context_empty_aad_matches_zero_tag_mode() {
+        let key = EncryptionKey::new(&EXAMPLE_KEY);
+        let plain_cruid = key.encrypt(42);
+        let context_cruid = key.encrypt_with_context(42, &[]);
+
+        assert_eq!(plain_cruid, context_cruid);
+        assert_eq!(key.decrypt_with_context(&plain_cruid, &[]).unwrap(), 42);
+        assert_eq!(key.decrypt(&context_cruid).unwrap(), 42);
+    }
+
+    #[cfg(feature = "kdf")]
+    #[test]
+    fn derive_from_password_deterministic() {
+        let params = KdfParams {
+            m_cost: 8,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let key_a =
+            EncryptionKey::derive_from_password_with_params(b"hunter2", b"salt1234", params)
+                .unwrap();
+        let key_b =
+            EncryptionKey::derive_from_password_with_params(b"hunter2", b"salt1234", params)
+                .unwrap();
+
+        assert_eq!(key_a.encrypt(42), key_b.encrypt(42));
+    }
+
+    #[cfg(feature = "kdf")]
+    #[test]
+    fn derive_from_password_distinct_salt() {
+        let params = KdfParams {
+            m_cost: 8,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let key_a =
+            EncryptionKey::derive_from_password_with_params(b"hunter2", b"salt1234", params)
+                .unwrap();
+        let key_b =
+            EncryptionKey::derive_from_password_with_params(b"hunter2", b"salt5678", params)
+                .unwrap();
+
+        assert_ne!(key_a.encrypt(42), key_b.encrypt(42));
+    }
 }
@@ -14,6 +14,10 @@ pub enum Error {
     /// Encoding is invalid.
     Encoding,
 
+    /// Key derivation failed.
+    #[cfg(feature = "kdf")]
+    KeyDerivation,
+
     /// Length is invalid.
     Length,
 }
@@ -38,6 +42,8 @@ impl fmt::Display for Error {
         f.write_str(match self {
             Error::Decryption => "decryption failed",
             Error::Encoding => "encoding invalid",
+            #[cfg(feature = "kdf")]
+            Error::KeyDerivation => "key derivation failed",
             Error::Length => "length invalid",
         })
     }
@@ -26,6 +26,8 @@ mod cruid;
 mod encryption;
 mod error;
 
+#[cfg(feature = "kdf")]
+pub use crate::encryption::KdfParams;
 pub use crate::{
     cruid::Cruid,
     encryption::EncryptionKey,